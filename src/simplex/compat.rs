@@ -0,0 +1,31 @@
+
+//! Adapter for the `noise` crate, enabled with the `noise-compat` feature.
+//!
+//! Simplex implements `NoiseFn<f64, 2>` and `NoiseFn<f64, 3>`, so it can be used
+//! anywhere a `noise` pipeline expects a source. \
+//! Like the generators in `noise`, the adapter returns values in range [-1, 1],
+//! ignoring the `max` and `min` of the Simplex.
+//! ```
+//! use denali::*;
+//! use noise::NoiseFn;
+//!
+//! let simplex = Simplex::default();
+//! assert!((simplex.get([5.0, 10.0]) - simplex.raw2D(5.0, 10.0) as f64).abs() < 1e-6);
+//! assert!((simplex.get([5.0, 10.0, 8.0]) - simplex.raw3D(5.0, 10.0, 8.0) as f64).abs() < 1e-6);
+//! ```
+
+use noise::NoiseFn;
+
+use super::Simplex;
+
+impl NoiseFn<f64, 2> for Simplex {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.raw2D(point[0] as f32, point[1] as f32) as f64
+    }
+}
+
+impl NoiseFn<f64, 3> for Simplex {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.raw3D(point[0] as f32, point[1] as f32, point[2] as f32) as f64
+    }
+}
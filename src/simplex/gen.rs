@@ -34,10 +34,23 @@ const PERMUTATION: [u8; 512] = [
     222, 114, 67, 29, 24, 72, 243, 141, 128, 195, 78, 66, 215, 61, 156, 180,
 ];
 
+/// Shuffles the permutation with the seed. \
+/// Only the first 256 entries are shuffled, and the second half is a copy of them,
+/// since the noise functions rely on `perm[i + 256] == perm[i]` when a cell wraps around.
+/// # Examples
+/// ```
+/// use denali::simplex::gen::get_perm;
+///
+/// let perm = get_perm(67893402);
+/// assert_eq!(perm[..256], perm[256..]);
+/// ```
 pub fn get_perm(seed: u128) -> [u8; 512] {
     let mut rng = Pcg64::new_seed(seed);
     let mut perm = PERMUTATION;
-    rng.shuffle(&mut perm);
+    rng.shuffle(&mut perm[..256]);
+    // the noise functions index past 256 when a cell wraps around,
+    // so the second half has to stay a copy of the first.
+    perm.copy_within(..256, 256);
     perm
 }
 
@@ -57,13 +70,16 @@ fn modulo(x: i32, m: i32) -> usize {
     }
 }
 
-/// Quickly finds the floor of a number faster than std can.
+/// Quickly finds the floor of a number faster than std can. \
+/// Truncation rounds towards 0, so only values that actually have a fraction below it step down,
+/// and whole numbers, including 0 and negative ones, floor to themselves.
 #[inline(always)]
 fn fast_floor(x: f32) -> i32 {
-    if x > 0.0 {
-        x as i32
+    let xi = x as i32;
+    if x < xi as f32 {
+        xi - 1
     } else {
-        x as i32 - 1
+        xi
     }
 }
 
@@ -106,8 +122,38 @@ fn gradient_1d(hash: u8, x: f32) -> f32 {
 const F2: f32 = 0.366025403;
 const G2: f32 = 0.211324865;
 
+/// The largest magnitude simplex2d was observed to output, measured over millions of samples. \
+/// The scale factor of 40 leaves the kernel short of [-1, 1], so this is used to stretch it back out.
+pub const SIMPLEX2D_OBSERVED_MAX: f32 = 0.8844;
+
+/// Estimates how far FBM of simplex2d reaches, after dividing by the sum of the amplitudes. \
+/// If the octaves were independent, the spread of the sum would shrink by `r = sqrt(sum of amp^2) / sum of amp`,
+/// and if they always peaked together it wouldn't shrink at all, so the true reach sits between `r` and 1. \
+/// The extremes of 100k samples were measured at `r^0.1` to `r^0.3` times SIMPLEX2D_OBSERVED_MAX,
+/// for 2 to 8 octaves and persistences of 0.3 to 0.7, and this uses `r^0.25`. \
+/// It is a heuristic rather than a bound, so samples past it are rare, but possible.
+pub fn fbm_observed_max(octaves: u8, persistence: f32) -> f32 {
+    let mut amp = 1.0;
+    let mut amp_sum = 0.0;
+    let mut amp_sq_sum = 0.0;
+    for _i in 0..octaves {
+        amp_sum += amp;
+        amp_sq_sum += amp * amp;
+        amp *= persistence;
+    }
+    let r = f32::sqrt(amp_sq_sum) / amp_sum;
+    SIMPLEX2D_OBSERVED_MAX * f32::sqrt(f32::sqrt(r))
+}
+
 #[inline(always)]
 pub fn simplex2d (x: f32, y: f32, perm: &[u8; 512]) -> f32 {
+    simplex2d_with_gradient(x, y, perm, gradient_2d)
+}
+
+/// Same as simplex2d, but uses `grad` in place of gradient_2d for the corner contributions. \
+/// `grad` receives the hashed corner value and the offset of the point from that corner.
+#[inline(always)]
+pub fn simplex2d_with_gradient (x: f32, y: f32, perm: &[u8; 512], grad: impl Fn(u8, f32, f32) -> f32) -> f32 {
 
     let s = (x + y) * F2;
     let xs = x + s;
@@ -144,30 +190,81 @@ pub fn simplex2d (x: f32, y: f32, perm: &[u8; 512]) -> f32 {
     let mut t = 0.5 - x_0 * x_0 - y_0 * y_0;
     if t >= 0.0 {
         t *= t;
-        n += t * t * gradient_2d(perm[ii + perm[jj as usize] as usize].into(), x_0, y_0);
+        n += t * t * grad(perm[ii + perm[jj] as usize], x_0, y_0);
     }
 
     let mut t = 0.5 - x1 * x1 - y1 * y1;
     if t >= 0.0 {
         t *= t;
-        n += t * t * gradient_2d(perm[ii + i1 as usize + perm[jj + j1 as usize] as usize].into(), x1, y1);
+        n += t * t * grad(perm[ii + i1 as usize + perm[jj + j1 as usize] as usize], x1, y1);
     }
 
     let mut t = 0.5 - x2 * x2 - y2 * y2;
     if t >= 0.0 {
         t *= t;
-        n += t * t * gradient_2d(perm[ii + 1 + perm[jj + 1] as usize].into(), x2, y2);
+        n += t * t * grad(perm[ii + 1 + perm[jj + 1] as usize], x2, y2);
     }
 
     // returns a number in range [0, 1]
     return 40.0 * n;
 }   
 
-/// This function is private and is not intended to be used by an end-user.
+/// Finds the skewed lattice cell `(i, j)` that the point falls in.\
+/// This is the same cell `simplex2d` uses to find its corners.
+#[inline(always)]
+pub fn simplex_cell2d (x: f32, y: f32) -> (i32, i32) {
+    let s = (x + y) * F2;
+    (fast_floor(x + s), fast_floor(y + s))
+}
+
+/// Hashes an integer cell into a stable 24-bit value using the permutation. \
+/// `salt` selects an independent stream for the same cell. \
+/// The permutation only sees the low 8 bits of each coordinate,
+/// so the full coordinates are mixed in at the end to keep the hash from repeating every 256 cells.
+#[inline(always)]
+pub fn cell_hash2d (i: i32, j: i32, salt: u8, perm: &[u8; 512]) -> u32 {
+    let ii = modulo(i, 256);
+    let jj = modulo(j, 256);
+
+    let h0 = perm[ii + perm[jj] as usize] as usize;
+    let h1 = perm[h0 + salt as usize] as usize;
+    let h2 = perm[h1 + jj] as usize;
+    let h3 = perm[h2 + ii] as usize;
+
+    let mut h = ((h1 << 16) | (h2 << 8) | h3) as u32;
+
+    // mix in the full coordinates
+    h ^= (i as u32).wrapping_mul(0x27d4eb2d) ^ (j as u32).wrapping_mul(0x165667b1);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b3c6d);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297a2d39);
+    h ^= h >> 15;
+
+    h & 0xffffff
+}
+
+/// Hashes a seed offset into a translation in noise space. \
+/// Both components are in range [0, 256), the period of the permutation,
+/// so every offset lands on a different part of the noise.
+#[inline(always)]
+pub fn seed_translation2d (seed_offset: u64) -> (f32, f32) {
+    // splitmix64 finalizer
+    let mut z = seed_offset.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^= z >> 31;
+
+    // 24 bits per axis, scaled to [0, 256)
+    let tx = (z >> 40) as f32 / 65536.0;
+    let ty = ((z >> 16) & 0xffffff) as f32 / 65536.0;
+    (tx, ty)
+}
+
 /// Function for simplex noise algorithm.
 /// Calculates gradients.
 #[inline(always)]
-fn gradient_2d(hash: u8, x: f32, y: f32) -> f32 {
+pub fn gradient_2d(hash: u8, x: f32, y: f32) -> f32 {
     let h = hash & 7;
 
     let mut u: f32 = if 4 > h { x } else { y };
@@ -288,3 +385,38 @@ fn gradient_3d(hash: i32, x: f32, y: f32, z: f32) -> f32 {
     let v = if (h < 4) { y } else { if (h == 12 || h == 14) { x } else { z } };
     (if (h & 1 != 0) { -u } else { u }) + (if (h & 2 != 0) { -v } else { v })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_is_continuous_where_cells_wrap() {
+        // the skewed x coordinate crosses 0 with y positive,
+        // which indexes into the second half of the permutation.
+        for seed in [1, 2, 67893402] {
+            let perm = get_perm(seed);
+            for y in [3.5, 10.25, 15.5238] {
+                for i in 0..4000 {
+                    let x = -y * 0.366 - 2.0 + i as f32 * 0.001;
+                    let step = (simplex2d(x + 0.001, y, &perm) - simplex2d(x, y, &perm)).abs();
+                    assert!(step < 0.05, "seed {} jumped by {} at ({}, {})", seed, step, x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fast_floor_matches_floor() {
+        for x in [0.0, -0.0, 1.0, -1.0, -2.0, 0.5, -0.5, 2.75, -2.75, 1e-6, -1e-6] {
+            assert_eq!(fast_floor(x), f32::floor(x) as i32, "fast_floor({})", x);
+        }
+    }
+
+    #[test]
+    fn lattice_vertex_is_the_first_corner_of_its_cell() {
+        // floor the skewed coordinate, so whole numbers are not pushed down a cell.
+        assert_eq!(simplex_cell2d(0.0, 0.0), (0, 0));
+        assert_eq!(simplex_cell2d(-1.0 + G2 * 2.0, -1.0 + G2 * 2.0), (-1, -1));
+    }
+}
@@ -5,6 +5,9 @@
 pub mod gen;
 use gen::*;
 
+#[cfg(feature = "noise-compat")]
+mod compat;
+
 /// Interface for working with Simplex Noise and Fractal Brownian Motion. \
 /// Can be used for both 2D and 3D noise values. \
 /// # Examples
@@ -91,6 +94,9 @@ pub struct Simplex {
     perm: [u8; 512],
     seed: u128,
 
+    /// How far raw2D is expected to reach, used by generate2D_unit.
+    unit_bound: f32,
+
 }
 
 impl Simplex {
@@ -100,7 +106,28 @@ impl Simplex {
         lacunarity: f32, persistence: f32, max: f32, min: f32, seed: u128
     ) -> Self {
         Self { octaves, x_frequency, y_frequency, z_frequency,
-               lacunarity, persistence, max, min, perm: get_perm(seed), seed }
+               lacunarity, persistence, max, min, perm: get_perm(seed), seed,
+               unit_bound: fbm_observed_max(octaves, persistence) }
+    }
+
+    /// Creates a Simplex with a hand-tuned set of parameters for a common type of terrain. \
+    /// The range is 0 to 255, the same as the default. See TerrainPreset for what each preset is for.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let hills = Simplex::preset(TerrainPreset::RollingHills, 67893402);
+    /// let n: f32 = hills.generate2D(5.0, 10.0);
+    /// ```
+    pub fn preset(preset: TerrainPreset, seed: u128) -> Self {
+        match preset {
+            // octaves, x_freq, y_freq, z_freq, lacunarity, persistence, max, min
+            TerrainPreset::RollingHills => Simplex::new(4, 0.004, 0.004, 0.004, 2.0, 0.4, 255.0, 0.0, seed),
+            TerrainPreset::Mountains    => Simplex::new(8, 0.005, 0.005, 0.005, 2.2, 0.6, 255.0, 0.0, seed),
+            TerrainPreset::Plains       => Simplex::new(2, 0.002, 0.002, 0.002, 2.0, 0.3, 255.0, 0.0, seed),
+            TerrainPreset::Islands      => Simplex::new(5, 0.004, 0.004, 0.004, 2.5, 0.45, 255.0, 0.0, seed),
+            TerrainPreset::Caves        => Simplex::new(3, 0.03, 0.03, 0.03, 2.0, 0.5, 255.0, 0.0, seed),
+        }
     }
 
     /// Change the range field of this noise generator. \
@@ -116,76 +143,349 @@ impl Simplex {
         self.perm = get_perm(seed);
     }
 
+    /// Recomputes the range generate2D_unit stretches the noise to. \
+    /// It is computed once by new, so call this after changing `octaves` or `persistence` directly.
+    #[inline]
+    pub fn calibrate(&mut self) {
+        self.unit_bound = fbm_observed_max(self.octaves, self.persistence);
+    }
+
     /// Generates a single noise value. \
     /// `x` and `y` are the input values, and dictate the algorithm on how to behave. \
     /// This function also applies Fractal Brownian Motion.
     pub fn generate2D (&self, x: f32, y: f32) -> f32 {
+        self.remap(self.raw2D(x, y))
+    }
+
+    /// Same as generate2D, but returns the value in range [-1, 1] instead of the target range.
+    pub fn raw2D (&self, x: f32, y: f32) -> f32 {
+        self.fbm2D(self.x_frequency, self.y_frequency, |xfreq, yfreq| {
+            simplex2d(x * xfreq, y * yfreq, &self.perm)
+        })
+    }
+
+    /// Same as generate2D, but the value is always in range [0, 1], ignoring `max` and `min`. \
+    /// Adding octaves narrows the output, so the raw value is stretched by fbm_observed_max for this generator's
+    /// octaves and persistence, and the rare overshoot is clamped. \
+    /// That stretch is a heuristic, so the ends of the range are approached, but not reached by any particular amount.
+    /// A generator with no octaves has no noise, and returns 0.5.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::preset(TerrainPreset::Mountains, 67893402);
+    /// let value = noise.generate2D_unit(5.0, 10.0);
+    /// assert!((0.0..=1.0).contains(&value));
+    /// ```
+    #[inline]
+    pub fn generate2D_unit (&self, x: f32, y: f32) -> f32 {
+        let unit = (self.raw2D(x, y) / self.unit_bound + 1.0) / 2.0;
+        // with no octaves both the noise and the bound are 0 / 0.
+        if unit.is_nan() { 0.5 } else { unit.clamp(0.0, 1.0) }
+    }
+
+    /// Same as generate2D, but uses `grad` in place of the built-in gradient function. \
+    /// `grad` receives the hashed corner value and the offset of the point from that corner,
+    /// and returns the dot product of the corner's gradient with that offset.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    /// use denali::simplex::gen::gradient_2d;
+    ///
+    /// let noise = Simplex::default();
+    /// assert_eq!(noise.generate2D_with_gradient(5.0, 10.0, gradient_2d), noise.generate2D(5.0, 10.0));
+    ///
+    /// // no corner contributes anything, so the value sits in the middle of the range.
+    /// assert_eq!(noise.generate2D_with_gradient(5.0, 10.0, |_, _, _| 0.0), 127.5);
+    /// ```
+    pub fn generate2D_with_gradient (&self, x: f32, y: f32, grad: impl Fn(u8, f32, f32) -> f32) -> f32 {
+        self.remap(self.fbm2D(self.x_frequency, self.y_frequency, |xfreq, yfreq| {
+            simplex2d_with_gradient(x * xfreq, y * yfreq, &self.perm, &grad)
+        }))
+    }
+
+    /// Same as generate2D, but fades out octaves that are too fine for the sampling rate. \
+    /// `dx` and `dy` are the screen-space derivatives of the coordinate, eg. the distance between neighbouring pixels. \
+    /// Each octave is weighted by `1 / (1 + (octave_freq * pixel_footprint)^2)`,
+    /// so detail that can't be resolved fades towards the middle of the range instead of aliasing.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// assert_eq!(noise.generate2D_filtered(5.0, 10.0, 0.0, 0.0), noise.generate2D(5.0, 10.0));
+    /// ```
+    pub fn generate2D_filtered (&self, x: f32, y: f32, dx: f32, dy: f32) -> f32 {
+        self.remap(self.fbm2D(self.x_frequency, self.y_frequency, |xfreq, yfreq| {
+            // size of a pixel in this octave's noise space.
+            let fx = dx * xfreq;
+            let fy = dy * yfreq;
+            let filter = 1.0 / (1.0 + fx * fx + fy * fy);
+
+            // filtered octaves are faded out, but still count towards the range.
+            filter * simplex2d(x * xfreq, y * yfreq, &self.perm)
+        }))
+    }
+
+    /// Reinterprets generate2D as a signed distance field, with `iso` as the surface. \
+    /// Positive values are inside the surface, where the noise is above `iso`, and negative values are outside. \
+    /// The difference is divided by the length of the local gradient, estimated with central differences,
+    /// so near the surface the magnitude is roughly the distance to it in input units.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let iso = noise.generate2D(5.0, 10.0);
+    /// assert!(noise.generate2D_sdf(5.0, 10.0, iso).abs() < 1e-3);
+    /// ```
+    pub fn generate2D_sdf (&self, x: f32, y: f32, iso: f32) -> f32 {
+        let distance = self.generate2D(x, y) - iso;
+
+        // step by a hundredth of the first octave's lattice.
+        let freq = f32::max(self.x_frequency.abs(), self.y_frequency.abs());
+        if freq == 0.0 {
+            return distance;
+        }
+        let h = 0.01 / freq;
+
+        let dx = (self.generate2D(x + h, y) - self.generate2D(x - h, y)) / (2.0 * h);
+        let dy = (self.generate2D(x, y + h) - self.generate2D(x, y - h)) / (2.0 * h);
+        let gradient = f32::sqrt(dx * dx + dy * dy);
+
+        // flat spots have no direction to the surface, so fall back to the difference.
+        if gradient > f32::EPSILON { distance / gradient } else { distance }
+    }
+
+    /// Same as generate2D, but rotates each octave by the golden angle (~137.5°) relative to the last. \
+    /// The simplex lattice of each octave then points in a different direction,
+    /// which hides the grid-aligned streaking that appears when octaves line up.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::new(4, 0.01, 0.01, 0.01, 2.0, 0.5, 255.0, 0.0, 67893402);
+    /// let n: f32 = noise.generate2D_rotated_octaves(5.0, 10.0);
+    /// ```
+    pub fn generate2D_rotated_octaves (&self, x: f32, y: f32) -> f32 {
+        // rotation of the current octave, starting unrotated.
+        let (step_sin, step_cos) = f32::sin_cos(GOLDEN_ANGLE);
+        let mut sin: f32 = 0.0;
+        let mut cos: f32 = 1.0;
+
+        self.remap(self.fbm2D(self.x_frequency, self.y_frequency, |xfreq, yfreq| {
+            // rotate the scaled coordinate, so the frequencies still apply along x and y.
+            let px = x * xfreq;
+            let py = y * yfreq;
+            let value = simplex2d(cos * px - sin * py, sin * px + cos * py, &self.perm);
+
+            // advance the rotation by the golden angle.
+            (sin, cos) = (sin * step_cos + cos * step_sin, cos * step_cos - sin * step_sin);
+            value
+        }))
+    }
+
+    /// Same as generate2D, but `x` and `y` are already multiplied by the starting frequencies. \
+    /// `generate2D_prescaled(x * x_frequency, y * y_frequency)` gives the same value as `generate2D(x, y)`,
+    /// up to floating point rounding, since each octave computes `(x * x_frequency) * lacunarity^n`
+    /// instead of `x * (x_frequency * lacunarity^n)`. x_frequency and y_frequency are not used.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let prescaled = noise.generate2D_prescaled(5.0 * noise.x_frequency, 10.0 * noise.y_frequency);
+    /// assert!((prescaled - noise.generate2D(5.0, 10.0)).abs() < 1e-3);
+    /// ```
+    pub fn generate2D_prescaled (&self, x: f32, y: f32) -> f32 {
+        // the frequencies are already applied, so only lacunarity is left.
+        self.remap(self.fbm2D(1.0, 1.0, |xfreq, yfreq| {
+            simplex2d(x * xfreq, y * yfreq, &self.perm)
+        }))
+    }
+
+    /// Same as generate2D, but translates each octave by an amount hashed from `seed_offset`. \
+    /// Different offsets produce decorrelated noise from the same permutation,
+    /// which is much cheaper than calling change_seed. The same offset always produces the same noise.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// assert_eq!(noise.generate2D_offset(5.0, 10.0, 1), noise.generate2D_offset(5.0, 10.0, 1));
+    /// assert_ne!(noise.generate2D_offset(5.0, 10.0, 1), noise.generate2D_offset(5.0, 10.0, 2));
+    /// ```
+    pub fn generate2D_offset (&self, x: f32, y: f32, seed_offset: u64) -> f32 {
+        // the translation is applied in noise space, so it doesn't lose precision at low frequencies.
+        let (tx, ty) = seed_translation2d(seed_offset);
+
+        self.remap(self.fbm2D(self.x_frequency, self.y_frequency, |xfreq, yfreq| {
+            simplex2d(x * xfreq + tx, y * yfreq + ty, &self.perm)
+        }))
+    }
+
+    /// Same as generate2D, but folds the input into mirrored tiles, like a kaleidoscope. \
+    /// `x` and `y` are passed through a triangle wave, `abs(((coord / fold_size) mod 2) - 1) * fold_size`,
+    /// so the noise is mirrored across every multiple of `fold_size`, including negative coordinates. \
+    /// If `fold_size` is 0, negative, or NaN, nothing is folded and this is the same as generate2D.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// // 3 past the fold at 64 mirrors 3 before it.
+    /// let n: f32 = noise.generate2D_folded(67.0, 10.0, 64.0);
+    /// assert!((n - noise.generate2D_folded(61.0, 10.0, 64.0)).abs() < 1e-3);
+    /// ```
+    #[inline]
+    pub fn generate2D_folded (&self, x: f32, y: f32, fold_size: f32) -> f32 {
+        self.generate2D(fold_coordinate(x, fold_size), fold_coordinate(y, fold_size))
+    }
+
+    /// Generates a single noise value. \
+    /// `x` and `y` are the input values, and dictate the algorithm on how to behave. \
+    /// This function also applies Fractal Brownian Motion.
+    pub fn generate3D (&self, x: f32, y: f32, z: f32) -> f32 {
+        self.remap(self.raw3D(x, y, z))
+    }
+
+    /// Same as generate3D, but returns the value in range [-1, 1] instead of the target range.
+    pub fn raw3D (&self, x: f32, y: f32, z: f32) -> f32 {
 
         // Create temporary values to hold sums
         let mut output: f32 = 0.0;
         let mut denom : f32 = 0.0;
-    
+
         // temp values to hold starting frequencies.
         let mut xfreq = self.x_frequency;
         let mut yfreq = self.y_frequency;
+        let mut zfreq = self.z_frequency;
 
         // amplitude always set to 1
         let mut amp = 1.0;
-    
+
         // octaves sets how many times we run this part
         for _i in 0..self.octaves {
             // add product of amp and the output of simplex3d to get the noise value for this octave. 
-            output += amp * simplex2d(x * xfreq, y * yfreq, &self.perm);
+            output += amp * simplex3d(x * xfreq, y * yfreq, z * zfreq, &self.perm);
             // add to denom so we can calculate range. 
             denom += amp;
 
             // multiply lacunarity to frequency.
             xfreq *= self.lacunarity;
             yfreq *= self.lacunarity;
+            zfreq *= self.lacunarity;
 
             // multiply amp by persistence. 
             amp *= self.persistence;
         }
 
-        // Calculate range and converted to target range.
-        (((output / denom) + 1.0) * (self.max - self.min)) / 2.0 + self.min
+        output / denom
     }
 
-    /// Generates a single noise value. \
-    /// `x` and `y` are the input values, and dictate the algorithm on how to behave. \
-    /// This function also applies Fractal Brownian Motion.
-    pub fn generate3D (&self, x: f32, y: f32, z: f32) -> f32 {
+    /// Applies Fractal Brownian Motion to a 2D sample, returning a value in range [-1, 1]. \
+    /// `sample` is called once per octave with that octave's x and y frequencies,
+    /// starting at `x_frequency` and `y_frequency`.
+    #[inline(always)]
+    fn fbm2D (&self, x_frequency: f32, y_frequency: f32, mut sample: impl FnMut(f32, f32) -> f32) -> f32 {
 
         // Create temporary values to hold sums
         let mut output: f32 = 0.0;
         let mut denom : f32 = 0.0;
-
+    
         // temp values to hold starting frequencies.
-        let mut xfreq = self.x_frequency;
-        let mut yfreq = self.y_frequency;
-        let mut zfreq = self.z_frequency;
+        let mut xfreq = x_frequency;
+        let mut yfreq = y_frequency;
 
         // amplitude always set to 1
         let mut amp = 1.0;
-
+    
         // octaves sets how many times we run this part
         for _i in 0..self.octaves {
-            // add product of amp and the output of simplex3d to get the noise value for this octave. 
-            output += amp * simplex3d(x * xfreq, y * yfreq, z * zfreq, &self.perm);
+            // add product of amp and the sample to get the noise value for this octave. 
+            output += amp * sample(xfreq, yfreq);
             // add to denom so we can calculate range. 
             denom += amp;
 
             // multiply lacunarity to frequency.
             xfreq *= self.lacunarity;
             yfreq *= self.lacunarity;
-            zfreq *= self.lacunarity;
 
             // multiply amp by persistence. 
             amp *= self.persistence;
         }
 
-        // Calculate range and converted to target range. 
-        (((output / denom) + 1.0) * (self.max - self.min)) / 2.0 + self.min
+        output / denom
+    }
+
+    /// Converts a value in range [-1, 1] to the target range.
+    #[inline(always)]
+    fn remap (&self, raw: f32) -> f32 {
+        ((raw + 1.0) * (self.max - self.min)) / 2.0 + self.min
+    }
+
+    /// Finds the skewed simplex lattice cell `(i, j)` that a point falls in. \
+    /// `x` and `y` are scaled by the starting frequencies, so the cell matches the first octave of generate2D. \
+    /// Every point inside a cell returns the same index, which makes it useful for placing one feature per cell.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// assert_eq!(noise.cell2D(10.0, 10.0), noise.cell2D(10.5, 10.0));
+    /// assert_ne!(noise.cell2D(10.0, 10.0), noise.cell2D(200.0, 10.0));
+    ///
+    /// // points on a lattice line belong to the cell above it.
+    /// assert_eq!(noise.cell2D(0.0, 0.0), (0, 0));
+    /// assert_eq!(noise.cell2D(-0.5, -0.5), (-1, -1));
+    /// ```
+    #[inline]
+    pub fn cell2D (&self, x: f32, y: f32) -> (i32, i32) {
+        simplex_cell2d(x * self.x_frequency, y * self.y_frequency)
+    }
+
+    /// Generates a stable random value in `[0, 1)` for an integer cell. \
+    /// The value is hashed from this generator's permutation and the cell coordinates, so it only changes with the seed.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let value = noise.cell_random2D(3, -7);
+    /// assert!((0.0..1.0).contains(&value));
+    /// assert_eq!(value, noise.cell_random2D(3, -7));
+    /// assert_ne!(value, noise.cell_random2D(4, -7));
+    /// assert_ne!(value, noise.cell_random2D(3, -6));
+    ///
+    /// // cells don't repeat with the period of the permutation.
+    /// assert_ne!(noise.cell_random2D(3, 5), noise.cell_random2D(259, 5));
+    /// assert_ne!(noise.cell_random2D(3, 5), noise.cell_random2D(3, 261));
+    /// assert_ne!(noise.cell_jitter2D(3, 5), noise.cell_jitter2D(259, 5));
+    /// ```
+    #[inline]
+    pub fn cell_random2D (&self, cell_x: i32, cell_y: i32) -> f32 {
+        cell_hash2d(cell_x, cell_y, 0, &self.perm) as f32 / 16777216.0
+    }
+
+    /// Generates a stable offset within an integer cell, with both components in `[0, 1)`. \
+    /// Add it to the cell coordinates to get a scattered position, eg. `(cell_x as f32 + jx, cell_y as f32 + jy)`. \
+    /// The offset is independent of cell_random2D for the same cell.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let (jx, jy) = noise.cell_jitter2D(3, -7);
+    /// assert!((0.0..1.0).contains(&jx) && (0.0..1.0).contains(&jy));
+    /// assert_eq!((jx, jy), noise.cell_jitter2D(3, -7));
+    /// assert_ne!((jx, jy), noise.cell_jitter2D(4, -7));
+    /// ```
+    #[inline]
+    pub fn cell_jitter2D (&self, cell_x: i32, cell_y: i32) -> (f32, f32) {
+        (
+            cell_hash2d(cell_x, cell_y, 1, &self.perm) as f32 / 16777216.0,
+            cell_hash2d(cell_x, cell_y, 2, &self.perm) as f32 / 16777216.0,
+        )
     }
 
     /// Same as generate2D, but takes the absolute value.\
@@ -245,6 +545,259 @@ impl Simplex {
         }
     }
 
+    /// Generates a single noise value at the center of a hexagon. \
+    /// `q` and `r` are axial coordinates on a grid of pointy-topped hexagons,
+    /// and `hex_size` is the distance from the center of a hexagon to its corners.
+    /// The center is found with hex_to_pixel, then sampled with generate2D.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let (x, y) = hex_to_pixel(2, -3, 4.0);
+    /// assert_eq!(noise.generate_hex(2, -3, 4.0), noise.generate2D(x, y));
+    /// ```
+    #[inline]
+    pub fn generate_hex (&self, q: i32, r: i32, hex_size: f32) -> f32 {
+        let (x, y) = hex_to_pixel(q, r, hex_size);
+        self.generate2D(x, y)
+    }
+
+    /// Generates a noisemap of values at the centers of a region of hexagons.\
+    /// * q_start -> the q offset for the axial coordinates
+    /// * r_start -> the r offset for the axial coordinates
+    /// * hex_size -> the distance from the center of a hexagon to its corners.
+    ///
+    /// * map -> A 1-dimensional array with 2-dimensions - q and r.
+    /// * map_width -> the q dimension of the array.
+    ///
+    /// `map[q + map_width * r]` is the value of the hexagon at `(q_start + q, r_start + r)`,
+    /// so the region is a parallelogram in axial coordinates.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let mut map = [0.0; 64];
+    /// noise.generate_hexmap(-4, 2, 4.0, &mut map, 8);
+    /// assert_eq!(map[3 + 8 * 5], noise.generate_hex(-1, 7, 4.0));
+    /// ```
+    pub fn generate_hexmap (&self, q_start: i32, r_start: i32, hex_size: f32, map: &mut [f32], map_width: usize) {
+        for q in 0..map_width {
+            for r in 0..(map.len() / map_width) {
+                map[q + map_width * r] = self.generate_hex(q_start + q as i32, r_start + r as i32, hex_size);
+            }
+        }
+    }
+
+    /// Generates a 3D volume of noise that tiles seamlessly on all three axes.\
+    /// * period_x -> the distance along x after which the noise repeats.
+    /// * period_y -> the distance along y after which the noise repeats.
+    /// * period_z -> the distance along z after which the noise repeats.
+    /// * width, height, depth -> the dimensions of the volume.
+    ///
+    /// Voxel `(x, y, z)` is sampled at coordinate `(x, y, z)`, the same as generate_noisemap3D,
+    /// and is stored at index `x + width * y + width * height * z`.
+    /// A volume whose dimensions equal its periods can be repeated without seams.
+    ///
+    /// ## Limitations
+    /// Mapping each axis onto a circle would need 6D noise, which this crate does not have.
+    /// Instead, each axis blends the noise with a copy of itself shifted by one period,
+    /// which takes 8 samples of generate3D per voxel. \
+    /// A plain weighted average of the copies would lose about 3x of the contrast in the middle of each period,
+    /// so the blend is of each copy's distance from the middle of the range, divided by `sqrt(sum of weight^2)`.
+    /// That keeps the contrast even across the period as long as the copies are unrelated,
+    /// which needs a period of at least a few wavelengths of the first octave.
+    /// Shorter periods overshoot the range, and the overshoot is clamped.
+    /// # Panics
+    /// If any period is not positive, since there is nothing to repeat.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::new(3, 0.1, 0.1, 0.1, 2.5, 0.5, 255.0, 0.0, 67893402);
+    /// let volume = noise.generate_tileable3D(16.0, 16.0, 16.0, 16, 16, 16);
+    /// assert_eq!(volume.len(), 16 * 16 * 16);
+    /// ```
+    pub fn generate_tileable3D (&self, period_x: f32, period_y: f32, period_z: f32, width: usize, height: usize, depth: usize) -> Vec<f32> {
+        assert!(period_x > 0.0 && period_y > 0.0 && period_z > 0.0, "tileable periods must be positive");
+
+        let mut map = vec![0.0; width * height * depth];
+        let mid = (self.max + self.min) / 2.0;
+        let (low, high) = (f32::min(self.min, self.max), f32::max(self.min, self.max));
+
+        for x in 0..width {
+            for y in 0..height {
+                for z in 0..depth {
+                    // wrap the coordinate into the first period.
+                    let px = f32::rem_euclid(x as f32, period_x);
+                    let py = f32::rem_euclid(y as f32, period_y);
+                    let pz = f32::rem_euclid(z as f32, period_z);
+
+                    // how far through the period we are on each axis.
+                    let tx = px / period_x;
+                    let ty = py / period_y;
+                    let tz = pz / period_z;
+
+                    // blend with the copies shifted back by one period,
+                    // so the end of each period lines up with its start.
+                    let mut deviation = 0.0;
+                    let mut weight_sq = 0.0;
+                    for (sx, wx) in [(0.0, 1.0 - tx), (period_x, tx)] {
+                        for (sy, wy) in [(0.0, 1.0 - ty), (period_y, ty)] {
+                            for (sz, wz) in [(0.0, 1.0 - tz), (period_z, tz)] {
+                                let weight = wx * wy * wz;
+                                deviation += weight * (self.generate3D(px - sx, py - sy, pz - sz) - mid);
+                                weight_sq += weight * weight;
+                            }
+                        }
+                    }
+
+                    // the weights sum to 1, so the average of unrelated copies has less spread than each copy.
+                    let value = mid + deviation / f32::sqrt(weight_sq);
+                    map[x + width * y + width * height * z] = value.clamp(low, high);
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Generates a noisemap that fades to `min` at its edges.\
+    /// * x_start -> the x offset for the x input values
+    /// * y_start -> the y offset for the y input values
+    /// * width -> the x dimension of the patch.
+    /// * height -> the y dimension of the patch.
+    ///
+    /// * map -> A 1-dimensional array with 2-dimensions - x and y.
+    /// * map_width -> the x dimension of the array.
+    ///
+    /// Each value is multiplied by a separable 2D Hann window, so the patch can be composited onto another texture without seams.
+    /// The edges of the patch are exactly `min`. \
+    /// With an odd `width` and `height` the center sample is left unmodified. An even dimension has no center sample,
+    /// so the window never quite reaches 1 on that axis, eg. an 8x8 patch peaks at about 0.9 of the way from `min`.
+    /// # Panics
+    /// If `width` is larger than `map_width`, since the rows of the patch would overlap.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let mut map = [0.0; 81];
+    /// noise.generate_windowed2D(0.0, 0.0, 9, 9, &mut map, 9);
+    ///
+    /// assert_eq!(map[0], noise.min);
+    /// assert_eq!(map[4 + 9 * 4], noise.generate2D(4.0, 4.0));
+    /// ```
+    pub fn generate_windowed2D (&self, x_start: f32, y_start: f32, width: usize, height: usize, map: &mut [f32], map_width: usize) {
+        assert!(width <= map_width, "the patch is wider than the map");
+        for x in 0..width {
+            for y in 0..height {
+                let value = self.generate2D(x_start + x as f32, y_start + y as f32);
+                let window = hann_window(x, width) * hann_window(y, height);
+                map[x + map_width * y] = (value - self.min) * window + self.min;
+            }
+        }
+    }
+
+    /// Computes summary statistics over a region of noise.\
+    /// * x_start -> the x offset for the x input values
+    /// * y_start -> the y offset for the y input values
+    /// * width -> the x dimension of the region.
+    /// * height -> the y dimension of the region.
+    ///
+    /// Returns `(min, max, mean, std_dev)` of the sampled values, computed in a single pass using Welford's algorithm.
+    /// The standard deviation is the population standard deviation of the region. \
+    /// An empty region, where `width` or `height` is 0, has nothing to summarise,
+    /// and returns `(f32::INFINITY, f32::NEG_INFINITY, 0.0, 0.0)`.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let flat = Simplex::new(3, 0.0, 0.0, 0.0, 2.5, 0.5, 255.0, 0.0, 67893402);
+    /// let (min, max, _mean, std_dev) = flat.stats2D(0.0, 0.0, 32, 32);
+    /// assert_eq!(min, max);
+    /// assert!(std_dev < 1e-3);
+    ///
+    /// let noise = Simplex::new(3, 0.05, 0.05, 0.05, 2.5, 0.5, 255.0, 0.0, 67893402);
+    /// let (min, max, mean, std_dev) = noise.stats2D(0.0, 0.0, 256, 256);
+    /// assert!(min < mean && mean < max);
+    /// assert!((mean - 127.5).abs() < 20.0);
+    /// assert!(std_dev > 10.0);
+    /// ```
+    pub fn stats2D (&self, x_start: f32, y_start: f32, width: usize, height: usize) -> (f32, f32, f32, f32) {
+        let mut min = f32::INFINITY;
+        let mut max = f32::NEG_INFINITY;
+
+        // running mean and sum of squared differences from the mean.
+        let mut mean: f32 = 0.0;
+        let mut m2: f32 = 0.0;
+        let mut count: f32 = 0.0;
+
+        for x in 0..width {
+            for y in 0..height {
+                let value = self.generate2D(x_start + x as f32, y_start + y as f32);
+
+                min = f32::min(min, value);
+                max = f32::max(max, value);
+
+                count += 1.0;
+                let delta = value - mean;
+                mean += delta / count;
+                m2 += delta * (value - mean);
+            }
+        }
+
+        let std_dev = if count > 0.0 { f32::sqrt(m2 / count) } else { 0.0 };
+        (min, max, mean, std_dev)
+    }
+
+    /// Renders a region of noise as ASCII art, useful for debugging in a terminal.\
+    /// * x_start -> the x offset for the x input values
+    /// * y_start -> the y offset for the y input values
+    /// * width -> the number of characters per line.
+    /// * height -> the number of lines.
+    ///
+    /// Each value is mapped onto the density ramp ` .:-=+*#%@` according to this generator's range,
+    /// so `min` becomes ` ` and `max` becomes `@`. Lines are separated by `\n`.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let noise = Simplex::default();
+    /// let art = noise.to_ascii(0.0, 0.0, 16, 4);
+    /// assert_eq!(art.lines().count(), 4);
+    /// assert!(art.lines().all(|line| line.chars().count() == 16));
+    ///
+    /// // a generator with no frequency samples the same point everywhere.
+    /// let flat = Simplex::new(3, 0.0, 0.0, 0.0, 2.5, 0.5, 255.0, 0.0, 67893402);
+    /// let art = flat.to_ascii(0.0, 0.0, 8, 3);
+    /// let first = art.chars().next().unwrap();
+    /// assert!(art.lines().all(|line| line.chars().all(|c| c == first)));
+    /// ```
+    pub fn to_ascii (&self, x_start: f32, y_start: f32, width: usize, height: usize) -> String {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+
+        let range = self.max - self.min;
+        let mut art = String::with_capacity((width + 1) * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let value = self.generate2D(x_start + x as f32, y_start + y as f32);
+
+                // position of the value within the range, in [0, 1].
+                let t = if range != 0.0 { ((value - self.min) / range).clamp(0.0, 1.0) } else { 0.0 };
+                let index = (t * (RAMP.len() - 1) as f32).round() as usize;
+                art.push(RAMP[index] as char);
+            }
+            if y + 1 < height {
+                art.push('\n');
+            }
+        }
+
+        art
+    }
+
 }
 
 impl Default for Simplex {
@@ -263,6 +816,28 @@ impl Default for Simplex {
     }
 }
 
+/// Hand-tuned parameter sets for Simplex::preset.
+/// # Examples
+/// ```
+/// use denali::*;
+///
+/// let mountains = Simplex::preset(TerrainPreset::Mountains, 67893402);
+/// let n: f32 = mountains.generate2D(5.0, 10.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerrainPreset {
+    /// Gentle, broad hills with little fine detail.
+    RollingHills,
+    /// Jagged peaks with lots of detail at every scale.
+    Mountains,
+    /// Mostly flat land with very slow changes.
+    Plains,
+    /// Large blobs that work well with a sea level threshold.
+    Islands,
+    /// Higher frequency noise, intended for generate3D with a density threshold.
+    Caves,
+}
+
 impl PartialEq for Simplex {
     fn eq(&self, other: &Self) -> bool {
         self.seed == other.seed
@@ -271,3 +846,358 @@ impl PartialEq for Simplex {
 
 unsafe impl Send for Simplex { }
 unsafe impl Sync for Simplex { }
+
+/// Converts axial hex coordinates to the center of the hexagon, for pointy-topped hexagons. \
+/// `hex_size` is the distance from the center of a hexagon to its corners.
+/// ```text
+/// x = hex_size * sqrt(3) * (q + r / 2)
+/// y = hex_size * 3/2 * r
+/// ```
+/// # Examples
+/// ```
+/// use denali::*;
+///
+/// let (x, y) = hex_to_pixel(1, 0, 1.0);
+/// assert!((x - 3f32.sqrt()).abs() < 1e-6 && y == 0.0);
+///
+/// let (x, y) = hex_to_pixel(0, 1, 2.0);
+/// assert!((x - 3f32.sqrt()).abs() < 1e-6 && y == 3.0);
+/// ```
+#[inline]
+pub fn hex_to_pixel(q: i32, r: i32, hex_size: f32) -> (f32, f32) {
+    const SQRT_3: f32 = 1.732_050_8;
+    let q = q as f32;
+    let r = r as f32;
+    (hex_size * SQRT_3 * (q + r / 2.0), hex_size * 1.5 * r)
+}
+
+/// Hann window value for sample `i` of `n`. \
+/// Mirrors `i` around the center so the window is exactly symmetric and exactly 0 at both ends.
+#[inline]
+fn hann_window(i: usize, n: usize) -> f32 {
+    if n < 2 {
+        return 1.0;
+    }
+    let i = usize::min(i, n - 1 - i);
+    let s = f32::sin(std::f32::consts::PI * i as f32 / (n - 1) as f32);
+    s * s
+}
+
+/// Folds a coordinate with a triangle wave of period `2 * fold_size`. \
+/// rem_euclid keeps the result in [0, fold_size] for negative coordinates as well. \
+/// A fold_size that isn't positive (including NaN) leaves the coordinate unfolded.
+#[inline]
+fn fold_coordinate(coord: f32, fold_size: f32) -> f32 {
+    if fold_size > 0.0 {
+        f32::abs(f32::rem_euclid(coord / fold_size, 2.0) - 1.0) * fold_size
+    } else {
+        coord
+    }
+}
+
+/// The golden angle in radians, used to rotate octaves in generate2D_rotated_octaves.
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEEDS: [u128; 4] = [1, 2, 99, 67893402];
+
+    /// Average difference between neighbouring samples one unit apart along x,
+    /// over a `size` by `size` grid of points `spacing` apart.
+    fn roughness(generate: impl Fn(f32, f32) -> f32, size: usize, spacing: f32) -> f32 {
+        let mut sum = 0.0;
+        for i in 0..size {
+            for j in 0..size {
+                let (x, y) = (i as f32 * spacing, j as f32 * spacing);
+                sum += (generate(x + 1.0, y) - generate(x, y)).abs();
+            }
+        }
+        sum / (size * size) as f32
+    }
+
+    #[test]
+    fn presets_get_rougher_in_order() {
+        let presets = [
+            TerrainPreset::Plains, TerrainPreset::RollingHills, TerrainPreset::Islands,
+            TerrainPreset::Mountains, TerrainPreset::Caves,
+        ];
+        for seed in SEEDS {
+            let roughness: Vec<f32> = presets.iter().map(|&preset| {
+                let noise = Simplex::preset(preset, seed);
+                roughness(|x, y| noise.generate2D(x, y), 64, 8.0)
+            }).collect();
+            for pair in roughness.windows(2) {
+                assert!(pair[1] > pair[0] * 1.2, "seed {}: {:?}", seed, roughness);
+            }
+        }
+    }
+
+    #[test]
+    fn filtering_smooths_large_footprints() {
+        for seed in SEEDS {
+            let noise = Simplex::new(3, 0.01, 0.01, 0.01, 2.5, 0.5, 255.0, 0.0, seed);
+            let filtered = |footprint: f32| {
+                roughness(|x, y| noise.generate2D_filtered(x, y, footprint, footprint), 16, 16.0)
+            };
+            let (coarse, fine) = (filtered(100.0), filtered(1.0));
+            assert!(coarse < fine / 2.0, "seed {}: {} is not much smoother than {}", seed, coarse, fine);
+        }
+    }
+
+    #[test]
+    fn unit_approaches_both_ends() {
+        for seed in SEEDS {
+            let generators = [
+                (Simplex::new(3, 0.05, 0.05, 0.05, 2.5, 0.5, 255.0, 0.0, seed), 1.0),
+                (Simplex::new(8, 0.05, 0.05, 0.05, 2.0, 0.5, 255.0, 0.0, seed), 1.0),
+                (Simplex::preset(TerrainPreset::Mountains, seed), 10.0),
+            ];
+            for (noise, step) in generators {
+                // 100,000 samples.
+                let mut lowest: f32 = 1.0;
+                let mut highest: f32 = 0.0;
+                for x in 0..400 {
+                    for y in 0..250 {
+                        let value = noise.generate2D_unit(x as f32 * step, y as f32 * step);
+                        assert!((0.0..=1.0).contains(&value));
+                        lowest = lowest.min(value);
+                        highest = highest.max(value);
+                    }
+                }
+                assert!(lowest < 0.05 && highest > 0.95, "seed {}: [{}, {}]", seed, lowest, highest);
+            }
+        }
+    }
+
+    #[test]
+    fn unit_without_octaves_is_the_middle() {
+        let noise = Simplex::new(0, 0.05, 0.05, 0.05, 2.0, 0.5, 255.0, 0.0, 67893402);
+        assert_eq!(noise.generate2D_unit(5.0, 10.0), 0.5);
+    }
+
+    #[test]
+    fn calibrate_follows_changed_octaves() {
+        let mut noise = Simplex::new(0, 0.05, 0.05, 0.05, 2.0, 0.5, 255.0, 0.0, 67893402);
+        noise.octaves = 8;
+        noise.calibrate();
+        let expected = Simplex::new(8, 0.05, 0.05, 0.05, 2.0, 0.5, 255.0, 0.0, 67893402);
+        assert_eq!(noise.generate2D_unit(5.0, 10.0), expected.generate2D_unit(5.0, 10.0));
+    }
+
+    /// Average difference across the wrap from the last face to the first,
+    /// and between neighbouring faces inside a cubic volume, along one axis.
+    fn seams(map: &[f32], size: usize, axis: usize) -> (f32, f32) {
+        let index = |k: usize, a: usize, b: usize| match axis {
+            0 => k + size * a + size * size * b,
+            1 => a + size * k + size * size * b,
+            _ => a + size * b + size * size * k,
+        };
+        let (mut wrap, mut inner) = (0.0, 0.0);
+        for a in 0..size {
+            for b in 0..size {
+                wrap += (map[index(size - 1, a, b)] - map[index(0, a, b)]).abs();
+                for k in 0..size - 1 {
+                    inner += (map[index(k + 1, a, b)] - map[index(k, a, b)]).abs();
+                }
+            }
+        }
+        let faces = (size * size) as f32;
+        (wrap / faces, inner / ((size - 1) as f32 * faces))
+    }
+
+    /// Population standard deviation.
+    fn std_dev(values: &[f32]) -> f32 {
+        let n = values.len() as f32;
+        let mean = values.iter().sum::<f32>() / n;
+        f32::sqrt(values.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / n)
+    }
+
+    #[test]
+    fn tileable_volume_is_continuous_across_the_wrap() {
+        for seed in SEEDS {
+            let noise = Simplex::new(3, 0.1, 0.1, 0.1, 2.5, 0.5, 255.0, 0.0, seed);
+            let volume = noise.generate_tileable3D(16.0, 16.0, 16.0, 16, 16, 16);
+            let mut plain = [0.0; 4096];
+            noise.generate_noisemap3D(0.0, 0.0, 0.0, &mut plain, 16, 16);
+
+            for axis in 0..3 {
+                // the tiled volume is about as smooth across the wrap as it is inside.
+                let (wrap, inner) = seams(&volume, 16, axis);
+                assert!(wrap < 1.5 * inner, "seed {} axis {}: wrap {} inner {}", seed, axis, wrap, inner);
+
+                // a plain noisemap has a visible seam.
+                let (wrap, inner) = seams(&plain, 16, axis);
+                assert!(wrap > 1.5 * inner, "seed {} axis {}: plain wrap {} inner {}", seed, axis, wrap, inner);
+            }
+        }
+    }
+
+    #[test]
+    fn tileable_volume_keeps_its_contrast() {
+        for seed in SEEDS {
+            let noise = Simplex::new(3, 0.25, 0.25, 0.25, 2.5, 0.5, 255.0, 0.0, seed);
+            let volume = noise.generate_tileable3D(32.0, 32.0, 32.0, 32, 32, 32);
+
+            // voxels within 4 of the middle of the period on every axis, and within 4 of a corner.
+            let near = |k: usize, target: usize| {
+                let d = (k as i32 - target as i32).rem_euclid(32);
+                d.min(32 - d) < 4
+            };
+            let (mut centre, mut corner) = (vec![], vec![]);
+            for x in 0..32 {
+                for y in 0..32 {
+                    for z in 0..32 {
+                        let value = volume[x + 32 * y + 1024 * z];
+                        if near(x, 16) && near(y, 16) && near(z, 16) {
+                            centre.push(value);
+                        } else if near(x, 0) && near(y, 0) && near(z, 0) {
+                            corner.push(value);
+                        }
+                    }
+                }
+            }
+
+            let (centre, corner) = (std_dev(&centre), std_dev(&corner));
+            assert!(centre > 0.75 * corner && centre < 1.33 * corner, "seed {}: centre {} corner {}", seed, centre, corner);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "tileable periods must be positive")]
+    fn tileable_volume_needs_a_period() {
+        Simplex::default().generate_tileable3D(0.0, 16.0, 16.0, 4, 4, 4);
+    }
+
+    #[test]
+    fn windowed_patch_is_symmetric() {
+        // the window is symmetric, so a flat generator produces a symmetric patch with `min` on every edge.
+        let flat = Simplex::new(3, 0.0, 0.0, 0.0, 2.5, 0.5, 255.0, 0.0, 67893402);
+        for size in [8, 9] {
+            let mut map = vec![0.0; size * size];
+            flat.generate_windowed2D(0.0, 0.0, size, size, &mut map, size);
+            for y in 0..size {
+                for x in 0..size {
+                    assert_eq!(map[x + size * y], map[(size - 1 - x) + size * y]);
+                    assert_eq!(map[x + size * y], map[x + size * (size - 1 - y)]);
+                }
+                assert_eq!(map[size * y], flat.min);
+                assert_eq!(map[y], flat.min);
+            }
+        }
+    }
+
+    #[test]
+    fn windowed_patch_peaks_below_the_value_when_even() {
+        let flat = Simplex::new(3, 0.0, 0.0, 0.0, 2.5, 0.5, 255.0, 0.0, 67893402);
+        let mut map = [0.0; 64];
+        flat.generate_windowed2D(0.0, 0.0, 8, 8, &mut map, 8);
+        let peak = map.iter().cloned().fold(f32::MIN, f32::max);
+        assert!(peak < flat.generate2D(0.0, 0.0) * 0.91);
+    }
+
+    #[test]
+    #[should_panic(expected = "the patch is wider than the map")]
+    fn windowed_patch_must_fit_in_a_row() {
+        let mut map = [0.0; 64];
+        Simplex::default().generate_windowed2D(0.0, 0.0, 9, 4, &mut map, 8);
+    }
+
+    #[test]
+    fn stats_of_an_empty_region() {
+        let noise = Simplex::default();
+        assert_eq!(noise.stats2D(0.0, 0.0, 0, 16), (f32::INFINITY, f32::NEG_INFINITY, 0.0, 0.0));
+        assert_eq!(noise.stats2D(0.0, 0.0, 16, 0), (f32::INFINITY, f32::NEG_INFINITY, 0.0, 0.0));
+    }
+
+    #[test]
+    fn sdf_sign_follows_the_noise() {
+        for seed in SEEDS {
+            let noise = Simplex::new(3, 0.01, 0.01, 0.01, 2.5, 0.5, 255.0, 0.0, seed);
+            let iso = 127.5;
+
+            // the sign matches which side of iso the noise is on, and crosses the contour along the way.
+            let mut flips = 0;
+            let mut was_inside = noise.generate2D_sdf(0.0, 10.0, iso) > 0.0;
+            for x in 0..1024 {
+                let (x, y) = (x as f32, 10.0);
+                let inside = noise.generate2D_sdf(x, y, iso) > 0.0;
+                assert_eq!(noise.generate2D(x, y) > iso, inside, "seed {} at {}", seed, x);
+                if inside != was_inside {
+                    flips += 1;
+                }
+                was_inside = inside;
+            }
+            assert!(flips > 0, "seed {} never crossed the contour", seed);
+        }
+    }
+
+    #[test]
+    fn rotated_octaves_break_lattice_alignment() {
+        // with a lacunarity of 2, every octave shares the lattice vertices of the first octave,
+        // where simplex noise is always 0. Rotating the octaves breaks that alignment.
+        let alignment = |generate: &dyn Fn(f32, f32) -> f32| -> f32 {
+            let mut sum = 0.0;
+            for i in 1..17 {
+                for j in 1..17 {
+                    let t = (i + j) as f32 * 0.211_324_87;
+                    sum += generate(i as f32 - t, j as f32 - t).abs();
+                }
+            }
+            sum / 256.0
+        };
+
+        for seed in SEEDS {
+            let noise = Simplex::new(4, 1.0, 1.0, 1.0, 2.0, 0.5, 1.0, -1.0, seed);
+            let aligned = alignment(&|x, y| noise.generate2D(x, y));
+            let rotated = alignment(&|x, y| noise.generate2D_rotated_octaves(x, y));
+            assert!(aligned < 1e-3, "seed {}: aligned {}", seed, aligned);
+            assert!(rotated > 0.05, "seed {}: rotated {}", seed, rotated);
+        }
+    }
+
+    #[test]
+    fn folding_mirrors_across_every_fold() {
+        let noise = Simplex::default();
+        let fold = 64.0;
+        for d in [0.5, 3.0, 17.25, 40.0] {
+            // mirrored across the fold at 64, and across the fold at 0.
+            assert!((noise.generate2D_folded(fold + d, 10.0, fold) - noise.generate2D_folded(fold - d, 10.0, fold)).abs() < 1e-3);
+            assert!((noise.generate2D_folded(-d, 10.0, fold) - noise.generate2D_folded(d, 10.0, fold)).abs() < 1e-3);
+        }
+
+        // continuous across the fold.
+        let a = noise.generate2D_folded(fold - 0.01, 10.0, fold);
+        let b = noise.generate2D_folded(fold + 0.01, 10.0, fold);
+        assert!((a - b).abs() < 0.1);
+    }
+
+    #[test]
+    fn folding_needs_a_positive_fold_size() {
+        let noise = Simplex::default();
+        for fold in [0.0, -64.0, f32::NAN] {
+            assert_eq!(noise.generate2D_folded(5.0, 10.0, fold), noise.generate2D(5.0, 10.0));
+        }
+    }
+
+    #[test]
+    fn neighbouring_hexes_are_closer_than_distant_ones() {
+        for seed in SEEDS {
+            let noise = Simplex::new(3, 0.01, 0.01, 0.01, 2.5, 0.5, 255.0, 0.0, seed);
+
+            let (mut near, mut far) = (0.0, 0.0);
+            for q in -16..16 {
+                for r in -16..16 {
+                    let value = noise.generate_hex(q, r, 2.0);
+                    // the six axial neighbours.
+                    for (dq, dr) in [(1, 0), (-1, 0), (0, 1), (0, -1), (1, -1), (-1, 1)] {
+                        near += (value - noise.generate_hex(q + dq, r + dr, 2.0)).abs() / 6.0;
+                    }
+                    far += (value - noise.generate_hex(q + 53, r - 37, 2.0)).abs();
+                }
+            }
+            assert!(near < far / 2.0, "seed {}: near {} far {}", seed, near, far);
+        }
+    }
+}
@@ -27,14 +27,59 @@ use super::*;
     blue[id] = value3;
 */
 
+/*
+    The reference samples rx and ry from the same fractal at nearly the same coordinate,
+    so the r stage moves both axes together and smears the result diagonally.
+    Like qy, ry swaps its axes, which decorrelates it from rx:
+
+    float ry = generate(y + weight*qy + e, x + weight*qx + f, &fractal);
+*/
+
 pub fn domain_warp2d (warp: &DomainWarp, x: f32, y: f32) -> f32 {
+    domain_warp_stages2d(warp, x, y).2
+}
+
+/// Same as domain_warp2d, but also samples simplex3 warped by only the q stage, and by only the r stage.
+pub fn domain_warp_stages2d (warp: &DomainWarp, x: f32, y: f32) -> (f32, f32, f32) {
 
     let qx = warp.simplex1.generate2D(x, y);
     let qy = warp.simplex1.generate2D(y + warp.warps[0], x + warp.warps[1]);
 
     let rx = warp.simplex2.generate2D(x + warp.weight * qx + warp.warps[2], y + warp.weight * qy + warp.warps[3]);
-    let ry = warp.simplex2.generate2D(x + warp.weight * qx + warp.warps[4], y + warp.weight * qy + warp.warps[4]);
+    let ry = warp.simplex2.generate2D(y + warp.weight * qy + warp.warps[4], x + warp.weight * qx + warp.warps[5]);
+
+    // r sampled at the unwarped coordinate, so it carries none of q.
+    let rx_only = warp.simplex2.generate2D(x + warp.warps[2], y + warp.warps[3]);
+    let ry_only = warp.simplex2.generate2D(y + warp.warps[4], x + warp.warps[5]);
+
+    (
+        warp.simplex3.generate2D(x + warp.weight * qx, y + warp.weight * qy),
+        warp.simplex3.generate2D(x + warp.weight * rx_only, y + warp.weight * ry_only),
+        warp.simplex3.generate2D(x + warp.weight * rx, y + warp.weight * ry),
+    )
+
+}
+
+// Offsets used to decorrelate the warp source between axes.
+const OFFSET_A: f32 = 5.2;
+const OFFSET_B: f32 = 1.3;
+const OFFSET_C: f32 = 2.8;
+
+pub fn warped2d (warped: &WarpedSimplex, x: f32, y: f32) -> f32 {
+
+    let qx = warped.warp_source.generate2D(x, y);
+    let qy = warped.warp_source.generate2D(y + OFFSET_A, x + OFFSET_B);
+
+    warped.simplex.generate2D(x + warped.weight * qx, y + warped.weight * qy)
+
+}
+
+pub fn warped3d (warped: &WarpedSimplex, x: f32, y: f32, z: f32) -> f32 {
+
+    let qx = warped.warp_source.generate3D(x, y, z);
+    let qy = warped.warp_source.generate3D(y + OFFSET_A, z + OFFSET_B, x + OFFSET_C);
+    let qz = warped.warp_source.generate3D(z + OFFSET_C, x + OFFSET_A, y + OFFSET_B);
 
-    warp.simplex3.generate2D(x + warp.weight * rx, y + warp.weight * ry)
+    warped.simplex.generate3D(x + warped.weight * qx, y + warped.weight * qy, z + warped.weight * qz)
 
-}
\ No newline at end of file
+}
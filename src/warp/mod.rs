@@ -19,8 +19,184 @@ impl DomainWarp {
         Self { simplex1, simplex2, simplex3, warps: warp_values, weight }
     }
 
+    /// Generates a single warped noise value. \
+    /// The coordinate is warped twice, first by `q` from simplex1, then by `r` from simplex2,
+    /// and the result is sampled from simplex3:
+    /// ```text
+    /// qx = simplex1(x, y)
+    /// qy = simplex1(y + warps[0], x + warps[1])
+    ///
+    /// rx = simplex2(x + weight * qx + warps[2], y + weight * qy + warps[3])
+    /// ry = simplex2(y + weight * qy + warps[4], x + weight * qx + warps[5])
+    ///
+    /// value = simplex3(x + weight * rx, y + weight * ry)
+    /// ```
+    /// The y components swap their axes, so they aren't correlated with the x components.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let warp = DomainWarp::new(
+    ///     Simplex::default(), Simplex::default(), Simplex::default(),
+    ///     [5.2, 1.3, 1.7, 9.2, 8.3, 2.8], 4.0,
+    /// );
+    /// let n: f32 = warp.generate2D(5.0, 10.0);
+    /// ```
     pub fn generate2D (&self, x: f32, y: f32) -> f32 {
         domain_warp2d (&self, x, y)
     }
 
-}
\ No newline at end of file
+    /// Same as generate2D, but also returns the final simplex sampled with each stage of the warp on its own. \
+    /// Returns `(q_warped, r_warped, value)`:
+    /// * q_warped -> simplex3 warped by only the coarse `q` stage.
+    /// * r_warped -> simplex3 warped by only the fine `r` stage, with `r` sampled at the unwarped coordinate.
+    /// * value -> the full warp, where `r` is sampled at the q-warped coordinate, the same as generate2D.
+    ///
+    /// All three are in simplex3's range, so they can be compared or blended directly.
+    /// # Examples
+    /// ```
+    /// use denali::*;
+    ///
+    /// let warp = DomainWarp::new(
+    ///     Simplex::default(), Simplex::default(), Simplex::default(),
+    ///     [5.2, 1.3, 1.7, 9.2, 8.3, 2.8], 4.0,
+    /// );
+    /// let (_q_warped, _r_warped, value) = warp.generate2D_stages(5.0, 10.0);
+    /// assert_eq!(value, warp.generate2D(5.0, 10.0));
+    /// ```
+    pub fn generate2D_stages (&self, x: f32, y: f32) -> (f32, f32, f32) {
+        domain_warp_stages2d (self, x, y)
+    }
+
+}
+
+/// A Simplex whose input coordinates are warped by another Simplex. \
+/// Created with `Simplex::warped`, it is a lighter-weight alternative to a full DomainWarp.
+/// # Examples
+/// ```
+/// use denali::*;
+///
+/// let noise = Simplex::default();
+/// let warp_source = Simplex::new(3, 0.02, 0.02, 0.02, 2.0, 0.5, 1.0, -1.0, 1234);
+///
+/// assert_eq!(noise.warped(warp_source, 0.0).generate2D(5.0, 10.0), noise.generate2D(5.0, 10.0));
+/// assert_ne!(noise.warped(warp_source, 40.0).generate2D(5.0, 10.0), noise.generate2D(5.0, 10.0));
+/// ```
+#[derive(Clone, Copy)]
+pub struct WarpedSimplex {
+    simplex: Simplex,
+    warp_source: Simplex,
+    weight: f32
+}
+
+impl WarpedSimplex {
+
+    pub fn new (simplex: Simplex, warp_source: Simplex, weight: f32) -> Self {
+        Self { simplex, warp_source, weight }
+    }
+
+    pub fn generate2D (&self, x: f32, y: f32) -> f32 {
+        warped2d (self, x, y)
+    }
+
+    pub fn generate3D (&self, x: f32, y: f32, z: f32) -> f32 {
+        warped3d (self, x, y, z)
+    }
+
+}
+
+impl Simplex {
+
+    /// Warps the input coordinates of this Simplex by `warp_source`, scaled by `weight`. \
+    /// A weight of 0 leaves the coordinates untouched.
+    pub fn warped (self, warp_source: Simplex, weight: f32) -> WarpedSimplex {
+        WarpedSimplex::new(self, warp_source, weight)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simplex(seed: u128) -> Simplex {
+        Simplex::new(3, 0.02, 0.02, 0.02, 2.0, 0.5, 1.0, -1.0, seed)
+    }
+
+    /// Pearson correlation of two equally long series.
+    fn correlation(a: &[f32], b: &[f32]) -> f32 {
+        let n = a.len() as f32;
+        let (ma, mb) = (a.iter().sum::<f32>() / n, b.iter().sum::<f32>() / n);
+        let (mut cov, mut va, mut vb) = (0.0, 0.0, 0.0);
+        for (a, b) in a.iter().zip(b) {
+            cov += (a - ma) * (b - mb);
+            va += (a - ma) * (a - ma);
+            vb += (b - mb) * (b - mb);
+        }
+        cov / f32::sqrt(va * vb)
+    }
+
+    #[test]
+    fn generate2D_follows_the_documented_formula() {
+        let (s1, s2, s3) = (simplex(1), simplex(2), simplex(3));
+        let (weight, warps) = (4.0, [5.2, 1.3, 1.7, 9.2, 8.3, 2.8]);
+        let warp = DomainWarp::new(s1, s2, s3, warps, weight);
+
+        for i in 0..16 {
+            for j in 0..16 {
+                let (x, y) = (i as f32 * 7.0 - 50.0, j as f32 * 7.0 - 50.0);
+                let q = (s1.generate2D(x, y), s1.generate2D(y + warps[0], x + warps[1]));
+                let r = (
+                    s2.generate2D(x + weight * q.0 + warps[2], y + weight * q.1 + warps[3]),
+                    s2.generate2D(y + weight * q.1 + warps[4], x + weight * q.0 + warps[5]),
+                );
+                assert_eq!(warp.generate2D(x, y), s3.generate2D(x + weight * r.0, y + weight * r.1));
+            }
+        }
+    }
+
+    #[test]
+    fn r_stage_axes_are_decorrelated() {
+        let (weight, warps) = (4.0, [5.2, 1.3, 1.7, 9.2, 8.3, 2.8]);
+        for seed in [1, 4, 7, 10] {
+            let (s1, s2) = (simplex(seed), simplex(seed + 1));
+
+            let (mut rx, mut ry) = (vec![], vec![]);
+            for i in 0..64 {
+                for j in 0..64 {
+                    let (x, y) = (i as f32 * 13.0, j as f32 * 13.0);
+                    let q = (s1.generate2D(x, y), s1.generate2D(y + warps[0], x + warps[1]));
+                    rx.push(s2.generate2D(x + weight * q.0 + warps[2], y + weight * q.1 + warps[3]));
+                    ry.push(s2.generate2D(y + weight * q.1 + warps[4], x + weight * q.0 + warps[5]));
+                }
+            }
+            let correlation = correlation(&rx, &ry);
+            assert!(correlation.abs() < 0.25, "seed {}: {}", seed, correlation);
+        }
+    }
+
+    #[test]
+    fn stages_sample_the_final_simplex() {
+        let (s1, s2, s3) = (simplex(1), simplex(2), simplex(3));
+        let (weight, warps) = (4.0, [5.2, 1.3, 1.7, 9.2, 8.3, 2.8]);
+        let warp = DomainWarp::new(s1, s2, s3, warps, weight);
+
+        for (x, y) in [(5.0, 10.0), (-31.5, 7.25), (120.0, -64.0)] {
+            let (q_warped, r_warped, value) = warp.generate2D_stages(x, y);
+            assert_eq!(value, warp.generate2D(x, y));
+
+            let q = (s1.generate2D(x, y), s1.generate2D(y + warps[0], x + warps[1]));
+            assert_eq!(q_warped, s3.generate2D(x + weight * q.0, y + weight * q.1));
+
+            let r = (s2.generate2D(x + warps[2], y + warps[3]), s2.generate2D(y + warps[4], x + warps[5]));
+            assert_eq!(r_warped, s3.generate2D(x + weight * r.0, y + weight * r.1));
+        }
+    }
+
+    #[test]
+    fn stages_without_weight_are_unwarped() {
+        let warp = DomainWarp::new(simplex(1), simplex(2), simplex(3), [5.2, 1.3, 1.7, 9.2, 8.3, 2.8], 0.0);
+        let unwarped = simplex(3).generate2D(5.0, 10.0);
+        assert_eq!(warp.generate2D_stages(5.0, 10.0), (unwarped, unwarped, unwarped));
+    }
+}